@@ -0,0 +1,196 @@
+#[macro_use]
+extern crate criterion;
+extern crate unique_state_disjoints;
+
+use std::collections::HashSet;
+use std::sync::mpsc::channel;
+use std::sync::Arc;
+use criterion::{black_box, BatchSize, BenchmarkId, Criterion};
+use unique_state_disjoints::{find_disjoint_words_async, find_unique_disjoints_async,
+                              generate_list_of_characters, merge_disjoints, shared_count,
+                              Comparisson};
+
+const SIZES: [usize; 3] = [16, 128, 1024];
+
+/// The original sorted-`Vec<char>` scan this crate used before the bitmask rewrite, kept here
+/// purely as a performance baseline.
+fn sorted_slice_disjoint(a: &[char], b: &[char]) -> bool {
+    let mut skip = 0;
+
+    for i in a {
+        for (index, j) in b.iter().skip(skip).enumerate() {
+            if i == j {
+                return false;
+            }
+            if let std::cmp::Ordering::Less = j.cmp(i) {
+                skip = index;
+            }
+        }
+    }
+
+    true
+}
+
+fn hashset_disjoint(a: &HashSet<char>, b: &HashSet<char>) -> bool {
+    a.is_disjoint(b)
+}
+
+/// Deterministic pseudo-words (base-26 counters) so the benchmark doesn't depend on the
+/// contents of `words.txt`/`states.txt`.
+fn sample_words(count: usize) -> String {
+    let mut words = String::new();
+
+    for i in 0..count {
+        let mut n = i + 1;
+        while n > 0 {
+            words.push((b'a' + (n % 26) as u8) as char);
+            n /= 26;
+        }
+        words.push('\n');
+    }
+
+    words
+}
+
+fn bench_disjoint_impls(c: &mut Criterion) {
+    for &size in SIZES.iter() {
+        let words = sample_words(size);
+        let charset = generate_list_of_characters(&words);
+
+        let sorted: Vec<Vec<char>> = words.lines()
+            .map(|word| {
+                let mut chars: Vec<char> = word.chars().collect();
+                chars.sort();
+                chars.dedup();
+                chars
+            })
+            .collect();
+
+        let sets: Vec<HashSet<char>> = words.lines().map(|word| word.chars().collect()).collect();
+
+        c.bench_with_input(BenchmarkId::new("bitmask", size), &charset, |b, charset| {
+            b.iter(|| {
+                for i in 0..charset.len() {
+                    for j in 0..charset.len() {
+                        black_box(shared_count(&charset[i], &charset[j]));
+                    }
+                }
+            })
+        });
+
+        c.bench_with_input(BenchmarkId::new("sorted_slice", size), &sorted, |b, sorted| {
+            b.iter(|| {
+                for i in 0..sorted.len() {
+                    for j in 0..sorted.len() {
+                        black_box(sorted_slice_disjoint(&sorted[i], &sorted[j]));
+                    }
+                }
+            })
+        });
+
+        c.bench_with_input(BenchmarkId::new("hashset", size), &sets, |b, sets| {
+            b.iter(|| {
+                for i in 0..sets.len() {
+                    for j in 0..sets.len() {
+                        black_box(hashset_disjoint(&sets[i], &sets[j]));
+                    }
+                }
+            })
+        });
+    }
+}
+
+/// Measures the full async pipeline stage-by-stage (charset build, disjoint find, unique find,
+/// merge) so the flame-graph isn't the only signal for where the time goes.
+fn bench_pipeline_stages(c: &mut Criterion) {
+    for &size in SIZES.iter() {
+        let words = sample_words(size);
+        let states = sample_words(size / 4 + 1);
+
+        c.bench_with_input(BenchmarkId::new("charset_build", size), &words, |b, words| {
+            b.iter(|| black_box(generate_list_of_characters(words)));
+        });
+
+        c.bench_with_input(BenchmarkId::new("find_disjoint_words", size),
+                           &(words.clone(), states.clone()),
+                           |b, (words, states)| {
+            b.iter_batched(|| {
+                                (Arc::new(generate_list_of_characters(states)),
+                                 Arc::new(generate_list_of_characters(words)))
+                            },
+                            |(state_charset, word_charset)| {
+                                let disjoints =
+                                    find_disjoint_words_async(state_charset, word_charset, 0, 4);
+                                black_box(disjoints.iter().count())
+                            },
+                            BatchSize::SmallInput);
+        });
+
+        c.bench_with_input(BenchmarkId::new("find_unique_disjoints", size),
+                           &(words.clone(), states.clone()),
+                           |b, (words, states)| {
+            b.iter_batched(|| {
+                                let state_charset = Arc::new(generate_list_of_characters(states));
+                                let word_charset = Arc::new(generate_list_of_characters(words));
+                                let disjoints =
+                                    find_disjoint_words_async(state_charset.clone(), word_charset, 0, 4);
+                                (state_charset, disjoints.into_iter().collect::<Vec<Comparisson>>())
+                            },
+                            |(state_charset, comparissons)| {
+                                let (tx, rx) = channel();
+                                for comparisson in comparissons {
+                                    tx.send(comparisson).expect("Failed to send between treads");
+                                }
+                                drop(tx);
+
+                                let unique_disjoints =
+                                    find_unique_disjoints_async(state_charset, rx, 0, 4);
+                                black_box(unique_disjoints.iter().count())
+                            },
+                            BatchSize::SmallInput);
+        });
+
+        c.bench_with_input(BenchmarkId::new("merge_disjoints", size),
+                           &(words.clone(), states.clone()),
+                           |b, (words, states)| {
+            b.iter_batched(|| {
+                                let state_charset = Arc::new(generate_list_of_characters(states));
+                                let word_charset = Arc::new(generate_list_of_characters(words));
+                                let disjoints =
+                                    find_disjoint_words_async(state_charset.clone(), word_charset, 0, 4);
+                                let unique_disjoints =
+                                    find_unique_disjoints_async(state_charset, disjoints, 0, 4);
+                                unique_disjoints.into_iter().collect::<Vec<Comparisson>>()
+                            },
+                            |comparissons| {
+                                let (tx, rx) = channel();
+                                for comparisson in comparissons {
+                                    tx.send(comparisson).expect("Failed to send between treads");
+                                }
+                                drop(tx);
+
+                                black_box(merge_disjoints(rx))
+                            },
+                            BatchSize::SmallInput);
+        });
+
+        c.bench_with_input(BenchmarkId::new("full_pipeline", size),
+                           &(words.clone(), states.clone()),
+                           |b, (words, states)| {
+            b.iter(|| {
+                let word_charset = Arc::new(generate_list_of_characters(words));
+                let state_charset = Arc::new(generate_list_of_characters(states));
+
+                let disjoints =
+                    find_disjoint_words_async(state_charset.clone(), word_charset, 0, 4);
+                let unique_disjoints =
+                    find_unique_disjoints_async(state_charset, disjoints, 0, 4);
+
+                black_box(merge_disjoints(unique_disjoints))
+            });
+        });
+    }
+}
+
+criterion_group!(benches, bench_disjoint_impls, bench_pipeline_stages);
+criterion_main!(benches);
@@ -0,0 +1,300 @@
+extern crate scoped_threadpool;
+extern crate flame;
+
+pub mod union_find;
+
+use std::fs::File;
+use std::io::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::sync::mpsc::{channel, Receiver};
+use std::thread;
+use scoped_threadpool::Pool;
+use union_find::UnionFind;
+
+pub struct Comparisson {
+    pub word: Arc<CharsetEntry>,
+    pub state: Arc<CharsetEntry>,
+    /// Number of letters the word and state have in common. `0` is a strict disjoint match.
+    pub shared: u32,
+}
+
+pub struct CharsetEntry {
+    pub original: String,
+    /// Bit `n` is set when the entry contains the ascii letter `'a' + n`.
+    pub mask: u32,
+    /// Any characters that fall outside `a-z` (digits, punctuation, non-ascii),
+    /// kept in a small set so those entries are still compared correctly.
+    pub extra: HashSet<char>,
+}
+
+/// Counts the letters two charset entries have in common, across both the bitmap and the
+/// non-ascii fallback set. A result of `0` means the two entries are strictly disjoint.
+pub fn shared_count(a: &CharsetEntry, b: &CharsetEntry) -> u32 {
+    let shared_letters = (a.mask & b.mask).count_ones();
+    let shared_extra = a.extra.intersection(&b.extra).count() as u32;
+
+    shared_letters + shared_extra
+}
+
+/// Assigns each entry in `entries` to a connected-component id, unioning any two entries that
+/// share a letter. Because every overlapping pair is unioned directly, two entries that land in
+/// different components are guaranteed to have a `shared_count` of `0` without needing to be
+/// compared at all; two entries in the same component may still turn out to share nothing if
+/// they're only linked transitively through a third entry.
+pub fn letter_component_ids(entries: &[Arc<CharsetEntry>]) -> Vec<usize> {
+    let mut uf = UnionFind::new(entries.len());
+
+    for i in 0..entries.len() {
+        for j in (i + 1)..entries.len() {
+            if shared_count(&entries[i], &entries[j]) > 0 {
+                uf.union(i, j);
+            }
+        }
+    }
+
+    (0..entries.len()).map(|i| uf.find(i)).collect()
+}
+
+/// Groups `entries` into connected components by unioning any two that share a letter. The
+/// result is the clusters of states/words that are transitively linked through shared letters;
+/// an entry with nothing in common with any other comes back as a singleton component.
+pub fn letter_components(entries: &[Arc<CharsetEntry>]) -> Vec<Vec<Arc<CharsetEntry>>> {
+    let _guard = flame::start_guard("letter_components");
+    let ids = letter_component_ids(entries);
+
+    let mut groups: HashMap<usize, Vec<Arc<CharsetEntry>>> = HashMap::new();
+    for (entry, id) in entries.iter().zip(ids) {
+        groups.entry(id).or_default().push(entry.clone());
+    }
+
+    groups.into_values().collect()
+}
+
+/// Create a `Vec` of `Comparisson`s from the mpsc `Receiver`, sorted by closeness of match
+/// (fewest shared letters first) so the best candidates surface before weaker ones. Consumes
+/// the receiver as the final sink of the pipeline, so this only returns once every upstream
+/// stage has finished.
+pub fn merge_disjoints(disjoints: Receiver<Comparisson>) -> Vec<Comparisson> {
+    let _guard = flame::start_guard("merge_disjoints");
+    let mut disjoint_vec = vec![];
+
+    for disjoint in disjoints {
+        disjoint_vec.push(disjoint);
+    }
+
+    disjoint_vec.sort_by(|x, y| {
+        x.shared.cmp(&y.shared).then_with(|| x.state.original.cmp(&y.state.original))
+    });
+    disjoint_vec
+}
+
+/// Pulls `Comparisson`s off `disjoints` as they arrive and keeps each one only if no other
+/// state shares `k` or fewer letters with its word (with `k = 0`: the word is disjoint from
+/// every other state). Runs on its own thread so it can start consuming
+/// before `find_disjoint_words_async` has finished producing.
+pub fn find_unique_disjoints_async(states: Arc<Vec<Arc<CharsetEntry>>>,
+                                    disjoints: Receiver<Comparisson>,
+                                    k: u32,
+                                    pool_size: u32)
+                                    -> Receiver<Comparisson> {
+    let (tx, rx) = channel();
+
+    thread::spawn(move || {
+        let _guard = flame::start_guard("find_unique_disjoints_async");
+        let mut pool = Pool::new(pool_size);
+
+        pool.scoped(|scope| {
+            for word_pair in disjoints.iter() {
+                let states = &states;
+                let tx = tx.clone();
+                scope.execute(move || {
+                    let mut fail = false;
+                    for state in states.iter() {
+                        if state.original != word_pair.state.original &&
+                           shared_count(state, &word_pair.word) <= k {
+                            fail = true;
+                            break;
+                        }
+                    }
+                    if !fail {
+                        tx.send(word_pair).expect("Failed to send between treads");
+                    }
+                });
+            }
+        });
+    });
+
+    rx
+}
+
+/// Produces every state/word pair sharing at most `k` letters onto a channel (with `k = 0`:
+/// every strictly disjoint pair). Runs on its own thread so the
+/// caller gets the `Receiver` immediately and downstream stages can start draining it while
+/// this one is still comparing.
+pub fn find_disjoint_words_async(states: Arc<Vec<Arc<CharsetEntry>>>,
+                                  words: Arc<Vec<Arc<CharsetEntry>>>,
+                                  k: u32,
+                                  pool_size: u32)
+                                  -> Receiver<Comparisson> {
+    let (tx, rx) = channel();
+
+    thread::spawn(move || {
+        let _guard = flame::start_guard("find_disjoint_words_async");
+        let mut pool = Pool::new(pool_size);
+
+        pool.scoped(|scope| {
+            for state in states.iter() {
+                let words = &words;
+                let tx = tx.clone();
+                scope.execute(move || {
+                    for word in words.iter() {
+                        let shared = shared_count(state, word);
+                        if shared <= k {
+                            tx.send(Comparisson {
+                                    word: word.clone(),
+                                    state: state.clone(),
+                                    shared,
+                                })
+                                .expect("Failed to send between treads");
+                        }
+                    }
+                });
+            }
+        });
+    });
+
+    rx
+}
+
+pub fn generate_list_of_characters(words: &str) -> Vec<Arc<CharsetEntry>> {
+    let _guard = flame::start_guard("generate_list_of_characters");
+    let lines = words.lines();
+
+    let mut word_list = match lines.size_hint() {
+        (lower, Some(upper)) => Vec::with_capacity(upper - lower),
+        _ => Vec::new(),
+    };
+
+    for word in lines {
+        let mut mask: u32 = 0;
+        let mut extra = HashSet::new();
+
+        for char in word.chars() {
+            if char == ' ' {
+                continue;
+            }
+
+            let lower = char.to_ascii_lowercase();
+            if lower.is_ascii_lowercase() {
+                let bit = lower as u32 - 'a' as u32;
+                mask |= 1 << bit;
+            } else {
+                extra.insert(char);
+            }
+        }
+
+        word_list.push(Arc::new(CharsetEntry {
+            original: word.to_owned(),
+            mask,
+            extra,
+        }));
+    }
+
+    word_list
+}
+
+/// Spawns a reader thread that reads `filename` and builds its charset, handing ownership of
+/// the file contents straight to `generate_list_of_characters` without copying back to the
+/// caller. Returns immediately so file I/O overlaps with whatever the caller does next.
+pub fn load_charset_async(filename: String) -> Receiver<Vec<Arc<CharsetEntry>>> {
+    let (tx, rx) = channel();
+
+    thread::spawn(move || {
+        let contents = open_word_list(&filename);
+        let charset = generate_list_of_characters(&contents);
+        tx.send(charset).expect("Failed to send charset between threads");
+    });
+
+    rx
+}
+
+pub fn open_word_list(filename: &str) -> String {
+    let _guard = flame::start_guard("open_word_list");
+    let mut f = match File::open(filename) {
+        Ok(f) => f,
+        Err(_) => panic!("Unable to open wordlist!"),
+    };
+
+    let mut s = String::new();
+
+    match f.read_to_string(&mut s) {
+        Ok(_) => {}
+        Err(_) => panic!("Unable to read from wordlist!"),
+    };
+
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared_count_is_zero_for_disjoint_entries() {
+        let entries = generate_list_of_characters("cat\ndog");
+        assert_eq!(shared_count(&entries[0], &entries[1]), 0);
+    }
+
+    #[test]
+    fn shared_count_counts_overlapping_letters() {
+        let entries = generate_list_of_characters("cat\ncot");
+        assert_eq!(shared_count(&entries[0], &entries[1]), 2);
+    }
+
+    #[test]
+    fn shared_count_falls_back_to_the_extra_set_for_non_ascii_letters() {
+        let entries = generate_list_of_characters("café\ndoré");
+        assert_eq!(shared_count(&entries[0], &entries[1]), 1);
+    }
+
+    #[test]
+    fn letter_component_ids_splits_unrelated_entries() {
+        let entries = generate_list_of_characters("cat\ncot\nfly");
+        let ids = letter_component_ids(&entries);
+        assert_eq!(ids[0], ids[1]);
+        assert_ne!(ids[0], ids[2]);
+    }
+
+    fn run_pipeline(states: &str, words: &str, k: u32) -> Vec<(String, String, u32)> {
+        let states = Arc::new(generate_list_of_characters(states));
+        let words = Arc::new(generate_list_of_characters(words));
+
+        let disjoints = find_disjoint_words_async(states.clone(), words, k, 2);
+        let unique_disjoints = find_unique_disjoints_async(states, disjoints, k, 2);
+
+        merge_disjoints(unique_disjoints)
+            .into_iter()
+            .map(|pair| (pair.state.original.clone(), pair.word.original.clone(), pair.shared))
+            .collect()
+    }
+
+    #[test]
+    fn pipeline_with_zero_tolerance_keeps_every_strictly_disjoint_pair() {
+        // "cat"/"cot" share 2 letters and "xyz"/"fly" share 1, so at k=0 neither state steals
+        // the other's word and both disjoint pairs make it through as unique.
+        let result = run_pipeline("cat\nxyz", "cot\nfly", 0);
+        assert_eq!(result,
+                   vec![("cat".to_owned(), "fly".to_owned(), 0),
+                        ("xyz".to_owned(), "cot".to_owned(), 0)]);
+    }
+
+    #[test]
+    fn pipeline_with_nonzero_tolerance_drops_pairs_that_stop_being_unique() {
+        // Raising k to 1 lets "xyz" also near-match "fly" (1 shared letter), so "cat"/"fly" is
+        // no longer the only near-disjoint match for "fly" and drops out; "xyz"/"cot" survives
+        // since "cat"/"cot" still isn't within tolerance.
+        let result = run_pipeline("cat\nxyz", "cot\nfly", 1);
+        assert_eq!(result, vec![("xyz".to_owned(), "cot".to_owned(), 0)]);
+    }
+}
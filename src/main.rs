@@ -1,180 +1,170 @@
-extern crate scoped_threadpool;
 extern crate flame;
+extern crate unique_state_disjoints;
 
-use std::io::prelude::*;
+use std::env;
 use std::fs::File;
-use std::sync::mpsc::{channel, Receiver};
-use scoped_threadpool::Pool;
-
-const POOL_SIZE: u32 = 4;
-
-struct Comparisson<'a> {
-    word: &'a CharsetEntry<'a>,
-    state: &'a CharsetEntry<'a>,
-}
-
-struct CharsetEntry<'a> {
-    original: &'a str,
-    chars: Vec<char>,
-}
-
-fn main() {
-    let _guard = flame::start_guard("main");
-    let words = open_word_list("words.txt");
-    let word_charset = generate_list_of_characters(&words);
-
-    let states = open_word_list("states.txt");
-    let state_charset = generate_list_of_characters(&states);
-
-    let disjoints = find_disjoint_words_async(&state_charset, &word_charset);
-    let unique_disjoints = find_unique_disjoints_async(&state_charset, &disjoints);
-
-    let final_disjoints = merge_disjoints(&unique_disjoints);
-
-    for word_pair in final_disjoints.iter() {
-        let word = word_pair.word.original;
-        let state = word_pair.state.original;
-        println!("{} => {}", state, word);
-    }
-    flame::dump_html(&mut File::create("flame-graph.html").unwrap()).unwrap();
+use std::sync::Arc;
+
+use unique_state_disjoints::{Comparisson, find_disjoint_words_async, find_unique_disjoints_async,
+                              letter_components, load_charset_async, merge_disjoints};
+
+/// The pair of input lists, pool size, matching tolerance, and output format for a run.
+/// Built once in `main` from the command-line arguments and threaded through the pipeline
+/// instead of reading a hardcoded const.
+struct Config {
+    words_file: String,
+    states_file: String,
+    pool_size: u32,
+    tolerance: u32,
+    format: OutputFormat,
+    flame_graph_path: Option<String>,
+    show_components: bool,
 }
 
-/// Determines whether or not two slices of chars are disjoint. For this function to work
-/// correctly, the slices _MUST_ be sorted. If they are not sorted, this function will not work
-/// correctly, as it is optimized for sorted slices.
-fn is_disjoint(a: &[char], b: &[char]) -> bool {
-    let mut skip = 0;
-
-    for i in a {
-        for (index, j) in b.iter().skip(skip).enumerate() {
-            if i == j {
-                return false;
-            }
-            if let std::cmp::Ordering::Less = j.cmp(i) {
-                skip = index;
+impl Config {
+    fn from_args() -> Config {
+        let mut words_file = "words.txt".to_owned();
+        let mut states_file = "states.txt".to_owned();
+        let mut pool_size = 4;
+        let mut tolerance = 0;
+        let mut format = OutputFormat::Plain;
+        let mut flame_graph_path = Some("flame-graph.html".to_owned());
+        let mut show_components = false;
+
+        let mut args = env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--words" => {
+                    words_file = args.next().expect("--words requires a file path");
+                }
+                "--states" => {
+                    states_file = args.next().expect("--states requires a file path");
+                }
+                "--pool-size" => {
+                    pool_size = args.next()
+                        .expect("--pool-size requires a number")
+                        .parse()
+                        .expect("--pool-size must be a positive integer");
+                    assert!(pool_size >= 1, "--pool-size must be at least 1");
+                }
+                "--tolerance" => {
+                    tolerance = args.next()
+                        .expect("--tolerance requires a number")
+                        .parse()
+                        .expect("--tolerance must be a non-negative integer");
+                }
+                "--format" => {
+                    format = match args.next().expect("--format requires a value").as_str() {
+                        "plain" => OutputFormat::Plain,
+                        "json" => OutputFormat::Json,
+                        "tsv" => OutputFormat::Tsv,
+                        other => panic!("Unknown output format: {}", other),
+                    };
+                }
+                "--flame-graph" => {
+                    flame_graph_path =
+                        Some(args.next().expect("--flame-graph requires a file path"));
+                }
+                "--no-flame-graph" => flame_graph_path = None,
+                "--components" => show_components = true,
+                other => panic!("Unknown argument: {}", other),
             }
         }
-    }
 
-    true
+        Config {
+            words_file,
+            states_file,
+            pool_size,
+            tolerance,
+            format,
+            flame_graph_path,
+            show_components,
+        }
+    }
 }
 
-/// Create a sorted `Vec` of `Comparisson`s from the mpsc `Receiver`.
-fn merge_disjoints<'a>(disjoints: &Receiver<Comparisson<'a>>) -> Vec<Comparisson<'a>> {
-    let _guard = flame::start_guard("merge_disjoints");
-    let mut disjoint_vec = vec![];
+/// How a matched state/word pair is printed. `Plain` is the original human-readable output;
+/// `Json` and `Tsv` are machine-readable modes for piping into other tools.
+enum OutputFormat {
+    Plain,
+    Json,
+    Tsv,
+}
 
-    for disjoint in disjoints {
-        disjoint_vec.push(disjoint);
+/// Escapes `"` and `\` (and control characters) so a string can be embedded in a JSON string
+/// literal without producing invalid output.
+fn escape_json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
     }
 
-    disjoint_vec.sort_by(|x, y| x.state.original.cmp(y.state.original));
-    disjoint_vec
+    escaped
 }
 
-fn find_unique_disjoints_async<'a>(states: &'a [CharsetEntry<'a>],
-                                   disjoints: &Receiver<Comparisson<'a>>)
-                                   -> Receiver<Comparisson<'a>> {
-    let _guard = flame::start_guard("find_unique_disjoints_async");
-    let mut pool = Pool::new(POOL_SIZE);
-
-    let (tx, rx) = channel();
-    pool.scoped(|scope| {
-        for word_pair in disjoints.iter() {
-            let state_from_pair = word_pair.state;
-            let word_from_pair = word_pair.word;
-            let tx = tx.clone();
-            scope.execute(move || {
-                let mut fail = false;
-                for state in states.iter() {
-                    if state.original != state_from_pair.original &&
-                       is_disjoint(&state.chars, &word_from_pair.chars) {
-                        fail = true;
-                        break;
-                    }
-                }
-                if !fail {
-                    tx.send(Comparisson {
-                            word: word_from_pair,
-                            state: state_from_pair,
-                        })
-                        .expect("Failed to send between treads");
-                }
-            });
+fn print_result(format: &OutputFormat, pair: &Comparisson) {
+    match *format {
+        OutputFormat::Plain => println!("{} => {}", pair.state.original, pair.word.original),
+        OutputFormat::Tsv => {
+            println!("{}\t{}\t{}", pair.state.original, pair.word.original, pair.shared)
         }
-    });
-
-    rx
+        OutputFormat::Json => {
+            println!("{{\"state\":\"{}\",\"word\":\"{}\",\"shared\":{}}}",
+                     escape_json_string(&pair.state.original),
+                     escape_json_string(&pair.word.original),
+                     pair.shared)
+        }
+    }
 }
 
-fn find_disjoint_words_async<'a>(states: &'a [CharsetEntry<'a>],
-                                 words: &'a [CharsetEntry<'a>])
-                                 -> Receiver<Comparisson<'a>> {
-    let _guard = flame::start_guard("find_disjoint_words_async");
-    let mut pool = Pool::new(POOL_SIZE);
-
-    let (tx, rx) = channel();
-    pool.scoped(|scope| {
-        for state in states.iter() {
-            let tx = tx.clone();
-            scope.execute(move || {
-                for word in words.iter() {
-                    if is_disjoint(&state.chars, &word.chars) {
-                        tx.send(Comparisson {
-                                word: word,
-                                state: state,
-                            })
-                            .expect("Failed to send between treads");
-                    }
-                }
-            });
-        }
-    });
+fn main() {
+    let _guard = flame::start_guard("main");
+    let config = Config::from_args();
 
-    rx
-}
+    let words_rx = load_charset_async(config.words_file.clone());
+    let states_rx = load_charset_async(config.states_file.clone());
 
-fn generate_list_of_characters<'a>(words: &'a str) -> Vec<CharsetEntry<'a>> {
-    let _guard = flame::start_guard("generate_list_of_characters");
-    let lines = words.lines();
+    let word_charset = Arc::new(words_rx.recv().expect("Failed to load word list"));
+    let state_charset = Arc::new(states_rx.recv().expect("Failed to load state list"));
 
-    let mut word_list = match lines.size_hint() {
-        (lower, Some(upper)) => Vec::with_capacity(upper - lower),
-        _ => Vec::new(),
-    };
+    if config.show_components {
+        let mut entries = Vec::with_capacity(state_charset.len() + word_charset.len());
+        entries.extend(state_charset.iter().cloned());
+        entries.extend(word_charset.iter().cloned());
 
-    for word in lines {
-        let mut chars = Vec::with_capacity(word.chars().count());
-        for char in word.chars() {
-            if char != ' ' && !chars.contains(&char) {
-                chars.push(char);
-            }
+        for component in letter_components(&entries) {
+            let names: Vec<&str> = component.iter().map(|e| e.original.as_str()).collect();
+            println!("{}", names.join(", "));
         }
 
-        chars.sort();
-
-        word_list.push(CharsetEntry {
-            original: word,
-            chars: chars,
-        });
+        return;
     }
 
-    word_list
-}
-
-fn open_word_list(filename: &str) -> String {
-    let _guard = flame::start_guard("open_word_list");
-    let mut f = match File::open(filename) {
-        Ok(f) => f,
-        Err(_) => panic!("Unable to open wordlist!"),
-    };
+    let disjoints = find_disjoint_words_async(state_charset.clone(),
+                                               word_charset,
+                                               config.tolerance,
+                                               config.pool_size);
+    let unique_disjoints = find_unique_disjoints_async(state_charset,
+                                                        disjoints,
+                                                        config.tolerance,
+                                                        config.pool_size);
 
-    let mut s = String::new();
+    let final_disjoints = merge_disjoints(unique_disjoints);
 
-    match f.read_to_string(&mut s) {
-        Ok(_) => {}
-        Err(_) => panic!("Unable to read from wordlist!"),
-    };
+    for word_pair in final_disjoints.iter() {
+        print_result(&config.format, word_pair);
+    }
 
-    s
+    if let Some(path) = config.flame_graph_path {
+        flame::dump_html(&mut File::create(path).unwrap()).unwrap();
+    }
 }
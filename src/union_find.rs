@@ -0,0 +1,91 @@
+/// A disjoint-set-union structure with path compression and union by rank, used to find
+/// connected components over an index space in near-linear time.
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+}
+
+impl UnionFind {
+    pub fn new(size: usize) -> UnionFind {
+        UnionFind {
+            parent: (0..size).collect(),
+            rank: vec![0; size],
+        }
+    }
+
+    /// Finds the representative of `x`'s set, compressing the path as it walks up.
+    pub fn find(&mut self, x: usize) -> usize {
+        if self.parent[x] != x {
+            let root = self.find(self.parent[x]);
+            self.parent[x] = root;
+        }
+
+        self.parent[x]
+    }
+
+    /// Merges the sets containing `a` and `b`, attaching the lower-rank root to the higher.
+    pub fn union(&mut self, a: usize, b: usize) {
+        let root_a = self.find(a);
+        let root_b = self.find(b);
+
+        if root_a == root_b {
+            return;
+        }
+
+        if self.rank[root_a] < self.rank[root_b] {
+            self.parent[root_a] = root_b;
+        } else if self.rank[root_a] > self.rank[root_b] {
+            self.parent[root_b] = root_a;
+        } else {
+            self.parent[root_b] = root_a;
+            self.rank[root_a] += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UnionFind;
+
+    #[test]
+    fn starts_with_every_element_in_its_own_set() {
+        let mut uf = UnionFind::new(4);
+        for i in 0..4 {
+            assert_eq!(uf.find(i), i);
+        }
+    }
+
+    #[test]
+    fn union_merges_two_sets() {
+        let mut uf = UnionFind::new(4);
+        uf.union(0, 1);
+        assert_eq!(uf.find(0), uf.find(1));
+        assert_ne!(uf.find(0), uf.find(2));
+    }
+
+    #[test]
+    fn union_is_transitive() {
+        let mut uf = UnionFind::new(4);
+        uf.union(0, 1);
+        uf.union(1, 2);
+        assert_eq!(uf.find(0), uf.find(2));
+        assert_ne!(uf.find(0), uf.find(3));
+    }
+
+    #[test]
+    fn union_of_equal_rank_sets_still_joins_them() {
+        // Both 0 and 1 start at rank 0, so this exercises the tie-breaking branch of `union`.
+        let mut uf = UnionFind::new(2);
+        uf.union(0, 1);
+        assert_eq!(uf.find(0), uf.find(1));
+    }
+
+    #[test]
+    fn union_of_same_set_is_a_no_op() {
+        let mut uf = UnionFind::new(3);
+        uf.union(0, 1);
+        let root_before = uf.find(0);
+        uf.union(0, 1);
+        assert_eq!(uf.find(0), root_before);
+    }
+}